@@ -0,0 +1,183 @@
+//! Derive macro for `specs::saveload::SaveLoadComponent`.
+//!
+//! `#[derive(SaveLoad)]` generates a mirror `<Name>Data` struct where every
+//! `Entity` field becomes the marker type and every `Option<Entity>` field
+//! becomes `Option<marker>`, with all other fields cloned through as-is.
+//! The impl then threads the entity/marker mapping closure through those
+//! fields on `save`/`load`, so components that hold cross-entity references
+//! can round-trip through a save file.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use syn::{Data, DeriveInput, Fields, Ident, Type};
+
+#[proc_macro_derive(SaveLoad)]
+pub fn derive_save_load(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = syn::parse(input).expect("Unable to parse derive input");
+
+    let name = &input.ident;
+    let data_name = Ident::new(&format!("{}Data", name), Span::call_site());
+
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => panic!("#[derive(SaveLoad)] only supports structs with named fields"),
+        },
+        _ => panic!("#[derive(SaveLoad)] only supports structs"),
+    };
+
+    let data_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = data_field_type(&field.ty);
+        quote! { pub #ident: #ty }
+    });
+
+    let save_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match entity_kind(&field.ty) {
+            EntityKind::Entity => quote! {
+                #ident: ids(self.#ident)
+                    .ok_or(::specs::saveload::ConvertError::MissingMarker)?
+            },
+            EntityKind::OptionEntity => quote! {
+                #ident: match self.#ident {
+                    Some(entity) => Some(
+                        ids(entity).ok_or(::specs::saveload::ConvertError::MissingMarker)?
+                    ),
+                    None => None,
+                }
+            },
+            EntityKind::Plain => quote! { #ident: ::std::clone::Clone::clone(&self.#ident) },
+        }
+    });
+
+    let load_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match entity_kind(&field.ty) {
+            EntityKind::Entity => quote! {
+                #ident: ids(data.#ident)
+                    .ok_or(::specs::saveload::ConvertError::MissingEntity)?
+            },
+            EntityKind::OptionEntity => quote! {
+                #ident: match data.#ident {
+                    Some(marker) => Some(
+                        ids(marker).ok_or(::specs::saveload::ConvertError::MissingEntity)?
+                    ),
+                    None => None,
+                }
+            },
+            EntityKind::Plain => quote! { #ident: data.#ident },
+        }
+    });
+
+    let expanded = quote! {
+        #[derive(Clone, Serialize, Deserialize)]
+        #[serde(bound = "")]
+        pub struct #data_name<__M>
+        where
+            __M: ::std::clone::Clone + ::serde::ser::Serialize + ::serde::de::DeserializeOwned,
+        {
+            #(#data_fields,)*
+            #[serde(skip)]
+            __marker: ::std::marker::PhantomData<__M>,
+        }
+
+        impl<__M> ::specs::saveload::SaveLoadComponent<__M> for #name
+        where
+            __M: ::std::clone::Clone + ::serde::ser::Serialize + ::serde::de::DeserializeOwned,
+        {
+            type Data = #data_name<__M>;
+            type Error = ::specs::saveload::ConvertError;
+
+            fn save<F>(&self, mut ids: F) -> Result<Self::Data, Self::Error>
+            where
+                F: FnMut(::specs::world::Entity) -> Option<__M>,
+            {
+                Ok(#data_name {
+                    #(#save_fields,)*
+                    __marker: ::std::marker::PhantomData,
+                })
+            }
+
+            fn load<F>(data: Self::Data, mut ids: F) -> Result<Self, Self::Error>
+            where
+                F: FnMut(__M) -> Option<::specs::world::Entity>,
+            {
+                Ok(#name {
+                    #(#load_fields,)*
+                })
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+enum EntityKind {
+    Entity,
+    OptionEntity,
+    Plain,
+}
+
+fn entity_kind(ty: &Type) -> EntityKind {
+    if type_is(ty, "Entity") {
+        EntityKind::Entity
+    } else if let Some(inner) = option_inner(ty) {
+        if type_is(inner, "Entity") {
+            EntityKind::OptionEntity
+        } else {
+            EntityKind::Plain
+        }
+    } else {
+        EntityKind::Plain
+    }
+}
+
+fn data_field_type(ty: &Type) -> proc_macro2::TokenStream {
+    match entity_kind(ty) {
+        EntityKind::Entity => quote! { __M },
+        EntityKind::OptionEntity => quote! { Option<__M> },
+        EntityKind::Plain => quote! { #ty },
+    }
+}
+
+/// Matches a type path whose last segment is `name`, ignoring any leading
+/// module path (so both `Entity` and `specs::world::Entity` match).
+fn type_is(ty: &Type, name: &str) -> bool {
+    match *ty {
+        Type::Path(ref path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.value().ident == name)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// If `ty` is `Option<T>`, returns `T`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    use syn::{GenericArgument, PathArguments};
+
+    let path = match *ty {
+        Type::Path(ref path) => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?.into_value();
+    if segment.ident != "Option" {
+        return None;
+    }
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ref ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+
+use saveload::details::{Components, EntityData, Storages};
+use saveload::marker::{Marker, MarkerAllocator};
+use storage::WriteStorage;
+use world::Entities;
+
+/// Deserializes a sequence of `EntityData` produced by [`super::ser::serialize`],
+/// resolving or allocating an entity for each marker and applying its
+/// components as they're read off the stream -- one entity at a time,
+/// mirroring the memory profile of the streaming serializer.
+pub fn deserialize<'a, M, T, E, D>(
+    entities: &Entities<'a>,
+    markers: &mut WriteStorage<'a, M>,
+    allocator: &mut M::Allocator,
+    storages: &mut <T as Storages<'a>>::WriteStorages,
+    deserializer: D,
+) -> Result<(), D::Error>
+where
+    M: Marker,
+    T: Components<M::Identifier, E>,
+    E: Display,
+    D: Deserializer<'a>,
+{
+    struct EntitySeqVisitor<'a, 'b, M, T, E>
+    where
+        M: Marker,
+        T: Components<M::Identifier, E>,
+    {
+        entities: &'b Entities<'a>,
+        markers: &'b mut WriteStorage<'a, M>,
+        allocator: &'b mut M::Allocator,
+        storages: &'b mut <T as Storages<'a>>::WriteStorages,
+        pd: PhantomData<E>,
+    }
+
+    impl<'de, 'a, 'b, M, T, E> Visitor<'de> for EntitySeqVisitor<'a, 'b, M, T, E>
+    where
+        M: Marker,
+        T: Components<M::Identifier, E>,
+        E: Display,
+    {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a sequence of EntityData")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            while let Some(data) = seq.next_element::<EntityData<M, E, T>>()? {
+                let EntityData {
+                    marker,
+                    version,
+                    components,
+                } = data;
+                let (entity, _) =
+                    self.allocator
+                        .retrieve_entity_internal(marker.id(), self.markers, self.entities);
+
+                // Resolve markers embedded *inside* a component's own data the
+                // same way as the top-level entity above: through the
+                // allocator, allocating a placeholder entity if this marker
+                // hasn't been reached in the stream yet. A bare allocator
+                // lookup would fail for forward references to an entity that
+                // gets visited later in this same `seq`.
+                let allocator = &mut *self.allocator;
+                let markers = &mut *self.markers;
+                let entities = self.entities;
+                T::load(entity, components, self.storages, version, |id| {
+                    Some(allocator.retrieve_entity_internal(id, markers, entities).0)
+                })
+                .map_err(de::Error::custom)?;
+            }
+            Ok(())
+        }
+    }
+
+    deserializer.deserialize_seq(EntitySeqVisitor {
+        entities,
+        markers,
+        allocator,
+        storages,
+        pd: PhantomData,
+    })
+}
+
+/// Reads a stream produced by `serialize_into`, in the given `format`, and
+/// drives [`deserialize`] over it without buffering the whole payload.
+pub fn deserialize_from<'a, M, T, E, R>(
+    entities: &Entities<'a>,
+    markers: &mut WriteStorage<'a, M>,
+    allocator: &mut M::Allocator,
+    storages: &mut <T as Storages<'a>>::WriteStorages,
+    reader: &mut R,
+    format: ::saveload::ser::Format,
+) -> Result<(), String>
+where
+    M: Marker,
+    T: Components<M::Identifier, E>,
+    E: Display,
+    R: io::Read,
+{
+    match format {
+        #[cfg(feature = "bincode")]
+        ::saveload::ser::Format::Bincode => {
+            let mut deserializer = ::bincode::Deserializer::with_reader(
+                reader,
+                ::bincode::DefaultOptions::new(),
+            );
+            deserialize(entities, markers, allocator, storages, &mut deserializer)
+                .map_err(|e| e.to_string())
+        }
+        #[cfg(feature = "json")]
+        ::saveload::ser::Format::Json => {
+            let mut deserializer = ::serde_json::Deserializer::from_reader(reader);
+            deserialize(entities, markers, allocator, storages, &mut deserializer)
+                .map_err(|e| e.to_string())
+        }
+    }
+}
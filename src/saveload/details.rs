@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::fmt;
 
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
@@ -9,10 +10,54 @@ use shred::SystemData;
 use storage::{ReadStorage, WriteStorage};
 use world::{Component, Entity};
 
+/// Error produced by a `#[derive(SaveLoad)]` component when an `Entity`
+/// it references can't be converted through the marker mapping: either
+/// the entity has no marker yet (while saving), or a marker in the saved
+/// data no longer resolves to a live entity (while loading).
+#[derive(Debug)]
+pub enum ConvertError {
+    /// An `Entity` field has no marker registered for it.
+    MissingMarker,
+    /// A marker field doesn't resolve to any existing `Entity`.
+    MissingEntity,
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConvertError::MissingMarker => write!(f, "entity has no marker"),
+            ConvertError::MissingEntity => write!(f, "marker resolves to no entity"),
+        }
+    }
+}
+
+impl Error for ConvertError {
+    fn description(&self) -> &str {
+        match *self {
+            ConvertError::MissingMarker => "entity has no marker",
+            ConvertError::MissingEntity => "marker resolves to no entity",
+        }
+    }
+}
+
+/// Current schema version for saves produced by this crate. Bump this
+/// whenever a widely-used component's `Data` layout changes in a way that
+/// older saves can no longer deserialize as-is.
+pub const SAVE_VERSION: u32 = 0;
+
+fn save_version() -> u32 {
+    SAVE_VERSION
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(bound = "")]
 pub struct EntityData<M: Marker, E, T: Components<M::Identifier, E>> {
     pub marker: M,
+    /// Schema version this entity's components were saved under.
+    /// Defaults to `SAVE_VERSION` so saves written before versioning
+    /// was introduced still deserialize.
+    #[serde(default = "save_version")]
+    pub version: u32,
     pub components: T::Data,
 }
 
@@ -27,6 +72,14 @@ pub trait SaveLoadComponent<M>: Component {
     /// Error may occur during serialization or deserialization of component
     type Error: Error;
 
+    /// Version of this component's `Data` layout. This crate doesn't read
+    /// or compare it itself -- `Components::load` only ever forwards the
+    /// stored `EntityData::version` to `load_versioned`. It exists purely
+    /// as a convenience constant for a component's own `load_versioned`
+    /// override to compare the incoming `stored_version` against when
+    /// deciding whether (and how) to migrate.
+    const CURRENT_VERSION: u32 = 0;
+
     /// Convert this component into serializable form (`Data`) using
     /// entity to marker mapping function
     fn save<F>(&self, ids: F) -> Result<Self::Data, Self::Error>
@@ -38,6 +91,18 @@ pub trait SaveLoadComponent<M>: Component {
     fn load<F>(data: Self::Data, ids: F) -> Result<Self, Self::Error>
     where
         F: FnMut(M) -> Option<Entity>;
+
+    /// Like `load`, but told which version `data` was saved under, so a
+    /// component whose `Data` layout has since changed can migrate it
+    /// (e.g. fill in a new field with a default, or reinterpret a renamed
+    /// one) instead of failing outright. Defaults to ignoring
+    /// `stored_version` and delegating to `load`.
+    fn load_versioned<F>(data: Self::Data, _stored_version: u32, ids: F) -> Result<Self, Self::Error>
+    where
+        F: FnMut(M) -> Option<Entity>,
+    {
+        Self::load(data, ids)
+    }
 }
 
 impl<C, M> SaveLoadComponent<M> for C
@@ -79,11 +144,15 @@ pub trait Components<M, E>: for<'a> Storages<'a> {
     where
         F: FnMut(Entity) -> Option<M>;
 
-    /// Loads `Component`s to entity from `Data` deserializable representation
+    /// Loads `Component`s to entity from `Data` deserializable representation.
+    /// `version` is the schema version the data was saved under (see
+    /// `EntityData::version`) and is passed down to each component's
+    /// `SaveLoadComponent::load_versioned`.
     fn load<'a, F>(
         entity: Entity,
         components: Self::Data,
         storages: &mut <Self as Storages<'a>>::WriteStorages,
+        version: u32,
         ids: F,
     ) -> Result<(), E>
     where
@@ -122,7 +191,7 @@ macro_rules! impl_components {
 
             #[allow(unused_variables, unused_mut, non_snake_case)]
             fn load<'a, F>(entity: Entity, components: ($(Option<$a::Data>,)*),
-                           storages: &mut ($(WriteStorage<'a, $a>,)*), mut ids: F)
+                           storages: &mut ($(WriteStorage<'a, $a>,)*), version: u32, mut ids: F)
                 -> Result<(), E>
                 where F: FnMut(M) -> Option<Entity>
             {
@@ -130,7 +199,7 @@ macro_rules! impl_components {
                 let ($(ref mut $b,)*) = *storages;
                 $(
                     if let Some(a) = $a {
-                        $b.insert(entity, $a::load(a, &mut ids)?);
+                        $b.insert(entity, $a::load_versioned(a, version, &mut ids)?);
                     } else {
                         $b.remove(entity);
                     }
@@ -0,0 +1,88 @@
+use std::fmt::Display;
+use std::io;
+
+use serde::ser::{SerializeSeq, Serializer};
+
+use join::Join;
+use saveload::details::{Components, EntityData, Storages};
+use saveload::marker::Marker;
+use storage::ReadStorage;
+use world::Entities;
+
+/// Serializes every entity carrying a `M` marker, one entity at a time,
+/// tagging each with `version` (see `EntityData::version`).
+///
+/// Unlike collecting `T::save` results into a `Vec<EntityData>` first, this
+/// drives `serializer`'s sequence directly and never holds more than one
+/// entity's serialized data in memory at once, which matters for worlds with
+/// a large number of entities.
+pub fn serialize<'a, M, T, E, S>(
+    entities: &Entities<'a>,
+    markers: &ReadStorage<'a, M>,
+    storages: &<T as Storages<'a>>::ReadStorages,
+    version: u32,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    M: Marker,
+    T: Components<M::Identifier, E>,
+    E: Display,
+    S: Serializer,
+{
+    let mut seq = serializer.serialize_seq(None)?;
+    for (entity, marker) in (entities, markers).join() {
+        let components = T::save(entity, storages, |e| markers.get(e).map(Marker::id))
+            .map_err(::serde::ser::Error::custom)?;
+        seq.serialize_element(&EntityData::<M, E, T> {
+            marker: marker.clone(),
+            version,
+            components,
+        })?;
+    }
+    seq.end()
+}
+
+/// Output format understood by [`serialize_into`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Compact binary encoding via `bincode`.
+    #[cfg(feature = "bincode")]
+    Bincode,
+    /// Human-readable encoding via `serde_json`.
+    #[cfg(feature = "json")]
+    Json,
+}
+
+/// Streams every marked entity's components straight into `writer`, tagged
+/// with `version`, in the given `format`, without buffering an intermediate
+/// container.
+///
+/// This is the `Write`-backed counterpart of [`serialize`], for the common
+/// case of persisting a world to a file or a memory-mapped buffer.
+pub fn serialize_into<'a, M, T, E, W>(
+    entities: &Entities<'a>,
+    markers: &ReadStorage<'a, M>,
+    storages: &<T as Storages<'a>>::ReadStorages,
+    version: u32,
+    writer: &mut W,
+    format: Format,
+) -> Result<(), String>
+where
+    M: Marker,
+    T: Components<M::Identifier, E>,
+    E: Display,
+    W: io::Write,
+{
+    match format {
+        #[cfg(feature = "bincode")]
+        Format::Bincode => {
+            let mut serializer = ::bincode::Serializer::new(writer, ::bincode::DefaultOptions::new());
+            serialize(entities, markers, storages, version, &mut serializer).map_err(|e| e.to_string())
+        }
+        #[cfg(feature = "json")]
+        Format::Json => {
+            let mut serializer = ::serde_json::Serializer::new(writer);
+            serialize(entities, markers, storages, version, &mut serializer).map_err(|e| e.to_string())
+        }
+    }
+}
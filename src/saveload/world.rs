@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+use serde::de::Deserializer;
+use serde::ser::Serializer;
+use shred::SystemData;
+
+use saveload::de;
+use saveload::details::{Components, Storages};
+use saveload::marker::Marker;
+use saveload::ser;
+use storage::{ReadStorage, WriteStorage};
+use world::{Entities, World};
+
+/// Extension trait that collapses the usual saveload boilerplate -- fetching
+/// the marker storage, every component storage, the allocator, and the
+/// entity/marker mapping closure -- into two calls on `World`.
+pub trait SaveLoadWorld {
+    /// Serializes every entity carrying an `M` marker, along with its `T`
+    /// components, into `serializer`, tagging the save with `version` (see
+    /// `EntityData::version`).
+    fn serialize<M, T, E, S>(&self, version: u32, serializer: S) -> Result<(), S::Error>
+    where
+        M: Marker,
+        T: Components<M::Identifier, E>,
+        E: Display,
+        S: Serializer;
+
+    /// Deserializes entities and their `T` components out of `deserializer`,
+    /// resolving existing entities or allocating new ones through `M`'s
+    /// `MarkerAllocator` as their markers are read.
+    fn deserialize<'de, M, T, E, D>(&self, deserializer: D) -> Result<(), D::Error>
+    where
+        M: Marker,
+        T: Components<M::Identifier, E>,
+        E: Display,
+        D: Deserializer<'de>;
+}
+
+impl SaveLoadWorld for World {
+    fn serialize<M, T, E, S>(&self, version: u32, serializer: S) -> Result<(), S::Error>
+    where
+        M: Marker,
+        T: Components<M::Identifier, E>,
+        E: Display,
+        S: Serializer,
+    {
+        let entities = Entities::fetch(&self.res);
+        let markers = ReadStorage::<M>::fetch(&self.res);
+        let storages = <T as Storages>::ReadStorages::fetch(&self.res);
+        ser::serialize::<M, T, E, S>(&entities, &markers, &storages, version, serializer).map(|_| ())
+    }
+
+    fn deserialize<'de, M, T, E, D>(&self, deserializer: D) -> Result<(), D::Error>
+    where
+        M: Marker,
+        T: Components<M::Identifier, E>,
+        E: Display,
+        D: Deserializer<'de>,
+    {
+        let entities = Entities::fetch(&self.res);
+        let mut markers = WriteStorage::<M>::fetch(&self.res);
+        let mut allocator = self.res.fetch_mut::<M::Allocator>();
+        let mut storages = <T as Storages>::WriteStorages::fetch(&self.res);
+        de::deserialize::<M, T, E, D>(
+            &entities,
+            &mut markers,
+            &mut allocator,
+            &mut storages,
+            deserializer,
+        )
+    }
+}
@@ -0,0 +1,104 @@
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate specs;
+#[macro_use]
+extern crate specs_derive;
+
+use std::collections::HashMap;
+
+use specs::prelude::*;
+use specs::saveload::{ConvertError, Marker, SaveLoadComponent, U64Marker, U64MarkerAllocator};
+
+#[derive(SaveLoad)]
+struct Targeting {
+    target: Entity,
+    backup: Option<Entity>,
+    range: f32,
+}
+
+impl Component for Targeting {
+    type Storage = VecStorage<Self>;
+}
+
+fn marked_world() -> (World, Entity, Entity) {
+    let mut world = World::new();
+    world.register::<U64Marker>();
+    world.add_resource(U64MarkerAllocator::new());
+
+    let mut allocator = world.write_resource::<U64MarkerAllocator>();
+    let target = world.create_entity().marked::<U64Marker>(&mut allocator).build();
+    let backup = world.create_entity().marked::<U64Marker>(&mut allocator).build();
+    drop(allocator);
+
+    (world, target, backup)
+}
+
+#[test]
+fn round_trips_entity_fields_through_markers() {
+    let (world, target, backup) = marked_world();
+    let markers = world.read_storage::<U64Marker>();
+
+    let ids: HashMap<Entity, U64Marker> = [target, backup]
+        .iter()
+        .map(|&e| (e, markers.get(e).cloned().unwrap()))
+        .collect();
+
+    let component = Targeting {
+        target,
+        backup: Some(backup),
+        range: 12.5,
+    };
+
+    let data = component
+        .save(|e| ids.get(&e).cloned())
+        .expect("every referenced entity has a marker");
+
+    let entities: HashMap<U64Marker, Entity> =
+        ids.iter().map(|(&e, &m)| (m, e)).collect();
+
+    let loaded =
+        Targeting::load(data, |m| entities.get(&m).cloned()).expect("every marker resolves");
+
+    assert_eq!(loaded.target, target);
+    assert_eq!(loaded.backup, Some(backup));
+    assert_eq!(loaded.range, 12.5);
+}
+
+#[test]
+fn save_fails_when_entity_has_no_marker() {
+    let (world, _target, _backup) = marked_world();
+    let unmarked = world.create_entity().build();
+
+    let component = Targeting {
+        target: unmarked,
+        backup: None,
+        range: 1.0,
+    };
+
+    match component.save(|_| None::<U64Marker>) {
+        Err(ConvertError::MissingMarker) => {}
+        other => panic!("expected MissingMarker, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn load_fails_when_marker_has_no_entity() {
+    let (world, target, _backup) = marked_world();
+    let markers = world.read_storage::<U64Marker>();
+    let marker = markers.get(target).cloned().unwrap();
+
+    let component = Targeting {
+        target,
+        backup: None,
+        range: 1.0,
+    };
+    let data = component
+        .save(|e| if e == target { Some(marker) } else { None })
+        .unwrap();
+
+    match Targeting::load(data, |_| None) {
+        Err(ConvertError::MissingEntity) => {}
+        other => panic!("expected MissingEntity, got {:?}", other.map(|_| ())),
+    }
+}
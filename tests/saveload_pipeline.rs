@@ -0,0 +1,102 @@
+extern crate serde_json;
+extern crate specs;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate specs_derive;
+
+use specs::prelude::*;
+use specs::saveload::{de, ser, Marker, Storages, U64Marker, U64MarkerAllocator};
+use specs::shred::SystemData;
+
+#[derive(SaveLoad)]
+struct Link {
+    next: Option<Entity>,
+}
+
+impl Component for Link {
+    type Storage = VecStorage<Self>;
+}
+
+/// A `#[derive(SaveLoad)]` component holding an `Entity` reference should
+/// round-trip through the real save/load pipeline (`ser::serialize` /
+/// `de::deserialize`), including a forward reference to an entity that's
+/// visited later in the entity stream.
+#[test]
+fn derived_component_round_trips_through_ser_de() {
+    let mut source = World::new();
+    source.register::<Link>();
+    source.register::<U64Marker>();
+    source.add_resource(U64MarkerAllocator::new());
+
+    let (a, b) = {
+        let mut allocator = source.write_resource::<U64MarkerAllocator>();
+        let a = source
+            .create_entity()
+            .marked::<U64Marker>(&mut allocator)
+            .build();
+        let b = source
+            .create_entity()
+            .marked::<U64Marker>(&mut allocator)
+            .build();
+        (a, b)
+    };
+    // `a` comes first in the entity stream but its `Link` points forward to
+    // `b`, which hasn't been visited yet when `a` is deserialized.
+    source
+        .write_storage::<Link>()
+        .insert(a, Link { next: Some(b) })
+        .unwrap();
+    source
+        .write_storage::<Link>()
+        .insert(b, Link { next: None })
+        .unwrap();
+
+    let bytes = {
+        let entities = source.entities();
+        let markers = source.read_storage::<U64Marker>();
+        let storages = <(Link,) as Storages>::ReadStorages::fetch(&source.res);
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut out);
+        ser::serialize::<U64Marker, (Link,), specs::saveload::ConvertError, _>(
+            &entities, &markers, &storages, 0, &mut serializer,
+        )
+        .unwrap();
+        out
+    };
+
+    let mut target = World::new();
+    target.register::<Link>();
+    target.register::<U64Marker>();
+    target.add_resource(U64MarkerAllocator::new());
+
+    {
+        let entities = target.entities();
+        let mut markers = target.write_storage::<U64Marker>();
+        let mut allocator = target.write_resource::<U64MarkerAllocator>();
+        let mut storages = <(Link,) as Storages>::WriteStorages::fetch(&target.res);
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        de::deserialize::<U64Marker, (Link,), specs::saveload::ConvertError, _>(
+            &entities,
+            &mut markers,
+            &mut allocator,
+            &mut storages,
+            &mut deserializer,
+        )
+        .unwrap();
+    }
+
+    let entities = target.entities();
+    let markers = target.read_storage::<U64Marker>();
+    let links = target.read_storage::<Link>();
+
+    let mut by_marker: Vec<(u64, Option<u64>)> = (&entities, &markers, &links)
+        .join()
+        .map(|(_, m, l)| (m.id(), l.next.and_then(|e| markers.get(e).map(Marker::id))))
+        .collect();
+    by_marker.sort_by_key(|&(id, _)| id);
+
+    assert_eq!(by_marker.len(), 2);
+    assert_eq!(by_marker[1].1, None);
+    assert_eq!(by_marker[0].1, Some(by_marker[1].0));
+}
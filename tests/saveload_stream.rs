@@ -0,0 +1,142 @@
+extern crate serde_json;
+extern crate specs;
+
+use specs::prelude::*;
+use specs::saveload::{de, ser, Components, EntityData, Storages, U64Marker, U64MarkerAllocator};
+use specs::shred::SystemData;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+struct Pos(f32, f32);
+
+impl Component for Pos {
+    type Storage = VecStorage<Self>;
+}
+
+fn populated_world() -> World {
+    let mut world = World::new();
+    world.register::<Pos>();
+    world.register::<U64Marker>();
+    world.add_resource(U64MarkerAllocator::new());
+
+    let mut allocator = world.write_resource::<U64MarkerAllocator>();
+    world
+        .create_entity()
+        .with(Pos(1.0, 2.0))
+        .marked::<U64Marker>(&mut allocator)
+        .build();
+    world
+        .create_entity()
+        .with(Pos(3.0, 4.0))
+        .marked::<U64Marker>(&mut allocator)
+        .build();
+    drop(allocator);
+
+    world
+}
+
+/// The streamed output should match what you'd get serializing a plain
+/// `Vec<EntityData>` built by hand -- streaming must not change the wire
+/// format, only how much of it is buffered at once.
+#[test]
+fn streamed_output_matches_buffered_baseline() {
+    let world = populated_world();
+
+    let entities = world.entities();
+    let markers = world.read_storage::<U64Marker>();
+    let storages = <(Pos,) as Storages>::ReadStorages::fetch(&world.res);
+
+    let mut buffered = Vec::new();
+    for (entity, marker) in (&entities, &markers).join() {
+        let components = <(Pos,) as Components<_, specs::error::NoError>>::save(entity, &storages, |e| {
+            markers.get(e).cloned()
+        })
+        .unwrap();
+        buffered.push(EntityData::<U64Marker, specs::error::NoError, (Pos,)> {
+            marker: marker.clone(),
+            version: 0,
+            components,
+        });
+    }
+    let expected = serde_json::to_string(&buffered).unwrap();
+
+    let streamed = {
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut out);
+        ser::serialize::<U64Marker, (Pos,), specs::error::NoError, _>(
+            &entities,
+            &markers,
+            &storages,
+            0,
+            &mut serializer,
+        )
+        .unwrap();
+        out
+    };
+
+    assert_eq!(String::from_utf8(streamed).unwrap(), expected);
+}
+
+/// What `ser::serialize` writes, `de::deserialize` should read back into an
+/// equivalent set of marked entities and components.
+#[test]
+fn deserialize_roundtrips_through_serialize() {
+    let source = populated_world();
+
+    let bytes = {
+        let entities = source.entities();
+        let markers = source.read_storage::<U64Marker>();
+        let storages = <(Pos,) as Storages>::ReadStorages::fetch(&source.res);
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut out);
+        ser::serialize::<U64Marker, (Pos,), specs::error::NoError, _>(
+            &entities, &markers, &storages, 0, &mut serializer,
+        )
+        .unwrap();
+        out
+    };
+
+    let mut original: Vec<(u64, Pos)> = {
+        let entities = source.entities();
+        let markers = source.read_storage::<U64Marker>();
+        let positions = source.read_storage::<Pos>();
+        (&entities, &markers, &positions)
+            .join()
+            .map(|(_, m, p)| (m.id(), *p))
+            .collect()
+    };
+    original.sort_by_key(|(id, _)| *id);
+
+    let mut target = World::new();
+    target.register::<Pos>();
+    target.register::<U64Marker>();
+    target.add_resource(U64MarkerAllocator::new());
+
+    {
+        let entities = target.entities();
+        let mut markers = target.write_storage::<U64Marker>();
+        let mut allocator = target.write_resource::<U64MarkerAllocator>();
+        let mut storages = <(Pos,) as Storages>::WriteStorages::fetch(&target.res);
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        de::deserialize::<U64Marker, (Pos,), specs::error::NoError, _>(
+            &entities,
+            &mut markers,
+            &mut allocator,
+            &mut storages,
+            &mut deserializer,
+        )
+        .unwrap();
+    }
+
+    let mut loaded: Vec<(u64, Pos)> = {
+        let entities = target.entities();
+        let markers = target.read_storage::<U64Marker>();
+        let positions = target.read_storage::<Pos>();
+        (&entities, &markers, &positions)
+            .join()
+            .map(|(_, m, p)| (m.id(), *p))
+            .collect()
+    };
+    loaded.sort_by_key(|(id, _)| *id);
+
+    assert_eq!(loaded, original);
+}
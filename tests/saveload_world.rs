@@ -0,0 +1,76 @@
+extern crate serde_json;
+extern crate specs;
+
+use specs::prelude::*;
+use specs::saveload::{SaveLoadWorld, U64Marker, U64MarkerAllocator};
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
+struct Pos(f32, f32);
+
+impl Component for Pos {
+    type Storage = VecStorage<Self>;
+}
+
+/// `SaveLoadWorld` should collapse the usual fetch-storages-then-stream
+/// boilerplate into two calls that round-trip a world's marked entities.
+#[test]
+fn world_serialize_deserialize_round_trips() {
+    let mut source = World::new();
+    source.register::<Pos>();
+    source.register::<U64Marker>();
+    source.add_resource(U64MarkerAllocator::new());
+
+    {
+        let mut allocator = source.write_resource::<U64MarkerAllocator>();
+        source
+            .create_entity()
+            .with(Pos(1.0, 2.0))
+            .marked::<U64Marker>(&mut allocator)
+            .build();
+        source
+            .create_entity()
+            .with(Pos(3.0, 4.0))
+            .marked::<U64Marker>(&mut allocator)
+            .build();
+    }
+
+    let bytes = {
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::new(&mut out);
+        SaveLoadWorld::serialize::<U64Marker, (Pos,), specs::error::NoError, _>(
+            &source,
+            0,
+            &mut serializer,
+        )
+        .unwrap();
+        out
+    };
+
+    let mut target = World::new();
+    target.register::<Pos>();
+    target.register::<U64Marker>();
+    target.add_resource(U64MarkerAllocator::new());
+
+    {
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        SaveLoadWorld::deserialize::<U64Marker, (Pos,), specs::error::NoError, _>(
+            &target,
+            &mut deserializer,
+        )
+        .unwrap();
+    }
+
+    let mut expected: Vec<Pos> = {
+        let positions = source.read_storage::<Pos>();
+        (&positions,).join().map(|(p,)| *p).collect()
+    };
+    expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut actual: Vec<Pos> = {
+        let positions = target.read_storage::<Pos>();
+        (&positions,).join().map(|(p,)| *p).collect()
+    };
+    actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    assert_eq!(actual, expected);
+}